@@ -1,21 +1,22 @@
-use std::collections::HashMap;
 use std::fs::{create_dir_all, remove_dir_all, File};
 use std::future::Future;
 use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
 
 use async_stream::stream;
 use chrono::{DateTime, Utc};
+use dashmap::DashMap;
 use futures::pin_mut;
 use futures::stream::StreamExt;
 use heed::{
     types::{ByteSlice, SerdeBincode},
-    Database, Env, EnvOpenOptions,
+    CompactionOption, Database, Env, EnvOpenOptions,
 };
 use log::debug;
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
-use tokio::{sync::{mpsc, oneshot, RwLock}};
+use tokio::sync::{mpsc, oneshot};
 use tokio::task::spawn_blocking;
 use uuid::Uuid;
 
@@ -30,7 +31,6 @@ use crate::index_controller::{
 use crate::option::IndexerOpts;
 
 pub type Result<T> = std::result::Result<T, IndexError>;
-type AsyncMap<K, V> = Arc<RwLock<HashMap<K, V>>>;
 type UpdateResult = std::result::Result<Processed<UpdateMeta, UResult>, Failed<UpdateMeta, String>>;
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -40,12 +40,42 @@ pub struct IndexMeta {
     created_at: DateTime<Utc>,
     updated_at: DateTime<Utc>,
     primary_key: Option<String>,
+    map_size: usize,
 }
 
+/// Shape `IndexMeta` had before `map_size` was added. `SerdeBincode` is positional, not
+/// self-describing, so a record written by a pre-upgrade binary can't be decoded as the current
+/// `IndexMeta` at all: `get_meta` falls back to this type to read it, then rewrites the record in
+/// the current format so later reads take the fast path.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+struct IndexMetaV0 {
+    uuid: Uuid,
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+    primary_key: Option<String>,
+}
+
+impl From<IndexMetaV0> for IndexMeta {
+    fn from(v0: IndexMetaV0) -> Self {
+        Self {
+            uuid: v0.uuid,
+            created_at: v0.created_at,
+            updated_at: v0.updated_at,
+            primary_key: v0.primary_key,
+            map_size: DEFAULT_MAP_SIZE,
+        }
+    }
+}
+
+/// `map_size` new indexes are opened with when the caller doesn't request a specific one.
+const DEFAULT_MAP_SIZE: usize = 4096 * 100_000;
+
 enum IndexMsg {
     CreateIndex {
         uuid: Uuid,
         primary_key: Option<String>,
+        map_size: usize,
         ret: oneshot::Sender<Result<IndexMeta>>,
     },
     Update {
@@ -83,6 +113,30 @@ enum IndexMsg {
         uuid: Uuid,
         ret: oneshot::Sender<Result<Option<IndexMeta>>>,
     },
+    Snapshot {
+        uuid: Uuid,
+        dst: PathBuf,
+        ret: oneshot::Sender<Result<()>>,
+    },
+    LoadSnapshot {
+        uuid: Uuid,
+        src: PathBuf,
+        ret: oneshot::Sender<Result<()>>,
+    },
+    ResizeIndex {
+        uuid: Uuid,
+        new_size: usize,
+        ret: oneshot::Sender<Result<()>>,
+    },
+    ClearDocuments {
+        uuid: Uuid,
+        ret: oneshot::Sender<Result<()>>,
+    },
+    DeleteDocuments {
+        uuid: Uuid,
+        external_ids: Vec<String>,
+        ret: oneshot::Sender<Result<()>>,
+    },
 }
 
 struct IndexActor<S> {
@@ -102,11 +156,18 @@ pub enum IndexError {
     UnexistingIndex,
     #[error("Heed error: {0}")]
     HeedError(#[from] heed::Error),
+    #[error("the index actor is not available")]
+    ActorUnavailable,
 }
 
 #[async_trait::async_trait]
 trait IndexStore {
-    async fn create_index(&self, uuid: Uuid, primary_key: Option<String>) -> Result<IndexMeta>;
+    async fn create_index(
+        &self,
+        uuid: Uuid,
+        primary_key: Option<String>,
+        map_size: usize,
+    ) -> Result<IndexMeta>;
     async fn update_index<R, F>(&self, uuid: Uuid, f: F) -> Result<R>
     where
         F: FnOnce(Index) -> Result<R> + Send + Sync + 'static,
@@ -114,17 +175,20 @@ trait IndexStore {
     async fn get(&self, uuid: Uuid) -> Result<Option<Index>>;
     async fn delete(&self, uuid: Uuid) -> Result<Option<Index>>;
     async fn get_meta(&self, uuid: Uuid) -> Result<Option<IndexMeta>>;
+    async fn snapshot(&self, uuid: Uuid, dst: PathBuf) -> Result<()>;
+    async fn load_snapshot(&self, uuid: Uuid, src: PathBuf) -> Result<()>;
+    async fn resize(&self, uuid: Uuid, new_size: usize) -> Result<()>;
 }
 
 impl<S: IndexStore + Sync + Send> IndexActor<S> {
     fn new(
         read_receiver: mpsc::Receiver<IndexMsg>,
         write_receiver: mpsc::Receiver<IndexMsg>,
+        options: &IndexerOpts,
         store: S,
     ) -> Result<Self> {
-        let options = IndexerOpts::default();
         let update_handler =
-            UpdateHandler::new(&options).map_err(|e| IndexError::Error(e.into()))?;
+            UpdateHandler::new(options).map_err(|e| IndexError::Error(e.into()))?;
         let update_handler = Arc::new(update_handler);
         let read_receiver = Some(read_receiver);
         let write_receiver = Some(write_receiver);
@@ -186,9 +250,10 @@ impl<S: IndexStore + Sync + Send> IndexActor<S> {
             CreateIndex {
                 uuid,
                 primary_key,
+                map_size,
                 ret,
             } => {
-                let _ = ret.send(self.handle_create_index(uuid, primary_key).await);
+                let _ = ret.send(self.handle_create_index(uuid, primary_key, map_size).await);
             }
             Update { ret, meta, data } => {
                 let _ = ret.send(self.handle_update(meta, data).await);
@@ -228,6 +293,29 @@ impl<S: IndexStore + Sync + Send> IndexActor<S> {
             GetMeta { uuid, ret } => {
                 let _ = ret.send(self.handle_get_meta(uuid).await);
             }
+            Snapshot { uuid, dst, ret } => {
+                let _ = ret.send(self.handle_snapshot(uuid, dst).await);
+            }
+            LoadSnapshot { uuid, src, ret } => {
+                let _ = ret.send(self.handle_load_snapshot(uuid, src).await);
+            }
+            ResizeIndex {
+                uuid,
+                new_size,
+                ret,
+            } => {
+                let _ = ret.send(self.handle_resize_index(uuid, new_size).await);
+            }
+            ClearDocuments { uuid, ret } => {
+                let _ = ret.send(self.handle_clear_documents(uuid).await);
+            }
+            DeleteDocuments {
+                uuid,
+                external_ids,
+                ret,
+            } => {
+                let _ = ret.send(self.handle_delete_documents(uuid, external_ids).await);
+            }
         }
     }
 
@@ -244,8 +332,9 @@ impl<S: IndexStore + Sync + Send> IndexActor<S> {
         &self,
         uuid: Uuid,
         primary_key: Option<String>,
+        map_size: usize,
     ) -> Result<IndexMeta> {
-        self.store.create_index(uuid, primary_key).await
+        self.store.create_index(uuid, primary_key, map_size).await
     }
 
     async fn handle_update(
@@ -341,6 +430,70 @@ impl<S: IndexStore + Sync + Send> IndexActor<S> {
         let result = self.store.get_meta(uuid).await?;
         Ok(result)
     }
+
+    async fn handle_snapshot(&self, uuid: Uuid, dst: PathBuf) -> Result<()> {
+        self.store.snapshot(uuid, dst).await
+    }
+
+    async fn handle_load_snapshot(&self, uuid: Uuid, src: PathBuf) -> Result<()> {
+        self.store.load_snapshot(uuid, src).await
+    }
+
+    async fn handle_resize_index(&self, uuid: Uuid, new_size: usize) -> Result<()> {
+        self.store.resize(uuid, new_size).await
+    }
+
+    async fn handle_clear_documents(&self, uuid: Uuid) -> Result<()> {
+        self.store
+            .update_index(uuid, |index| {
+                let mut wtxn = index.0.env.write_txn()?;
+                milli::update::ClearDocuments::new(&mut wtxn, &index.0)
+                    .execute()
+                    .map_err(|e| IndexError::Error(e.into()))?;
+                wtxn.commit()?;
+                Ok(())
+            })
+            .await
+    }
+
+    async fn handle_delete_documents(&self, uuid: Uuid, external_ids: Vec<String>) -> Result<()> {
+        self.store
+            .update_index(uuid, move |index| {
+                let mut wtxn = index.0.env.write_txn()?;
+                let mut builder = milli::update::DeleteDocuments::new(&mut wtxn, &index.0)
+                    .map_err(|e| IndexError::Error(e.into()))?;
+                for external_id in &external_ids {
+                    builder.delete_external_id(external_id);
+                }
+                builder
+                    .execute()
+                    .map_err(|e| IndexError::Error(e.into()))?;
+                wtxn.commit()?;
+                Ok(())
+            })
+            .await
+    }
+}
+
+/// Forwards `msg` to the actor, turning a closed mpsc channel (the actor task has died) into
+/// `IndexError::ActorUnavailable` instead of silently dropping the message.
+async fn send_or_unavailable(sender: &mpsc::Sender<IndexMsg>, msg: IndexMsg) -> Result<()> {
+    sender
+        .send(msg)
+        .await
+        .map_err(|_| IndexError::ActorUnavailable)
+}
+
+/// Awaits the reply to a message, turning a dropped oneshot sender (the actor panicked before
+/// answering) into `IndexError::ActorUnavailable` instead of panicking.
+async fn recv_or_unavailable<T>(receiver: oneshot::Receiver<T>) -> Result<T> {
+    receiver.await.map_err(|_| IndexError::ActorUnavailable)
+}
+
+/// Flattens a `spawn_blocking` task's result, turning a crashed blocking worker (`JoinError`)
+/// into a regular `IndexError` instead of propagating its panic to the caller.
+fn flatten_blocking<T>(result: std::result::Result<Result<T>, tokio::task::JoinError>) -> Result<T> {
+    result.unwrap_or_else(|e| Err(IndexError::Error(e.into())))
 }
 
 #[derive(Clone)]
@@ -350,12 +503,12 @@ pub struct IndexActorHandle {
 }
 
 impl IndexActorHandle {
-    pub fn new(path: impl AsRef<Path>) -> anyhow::Result<Self> {
+    pub fn new(path: impl AsRef<Path>, options: &IndexerOpts) -> anyhow::Result<Self> {
         let (read_sender, read_receiver) = mpsc::channel(100);
         let (write_sender, write_receiver) = mpsc::channel(100);
 
-        let store = HeedIndexStore::new(path)?;
-        let actor = IndexActor::new(read_receiver, write_receiver, store)?;
+        let store = HeedIndexStore::new(path, options.max_open_indexes)?;
+        let actor = IndexActor::new(read_receiver, write_receiver, options, store)?;
         tokio::task::spawn(actor.run());
         Ok(Self {
             read_sender,
@@ -363,15 +516,32 @@ impl IndexActorHandle {
         })
     }
 
-    pub async fn create_index(&self, uuid: Uuid, primary_key: Option<String>) -> Result<IndexMeta> {
+    /// Creates `uuid` with the default `map_size`. See `create_index_with_map_size` to pick one
+    /// explicitly.
+    pub async fn create_index(
+        &self,
+        uuid: Uuid,
+        primary_key: Option<String>,
+    ) -> Result<IndexMeta> {
+        self.create_index_with_map_size(uuid, primary_key, DEFAULT_MAP_SIZE)
+            .await
+    }
+
+    pub async fn create_index_with_map_size(
+        &self,
+        uuid: Uuid,
+        primary_key: Option<String>,
+        map_size: usize,
+    ) -> Result<IndexMeta> {
         let (ret, receiver) = oneshot::channel();
         let msg = IndexMsg::CreateIndex {
             ret,
             uuid,
             primary_key,
+            map_size,
         };
-        let _ = self.read_sender.send(msg).await;
-        receiver.await.expect("IndexActor has been killed")
+        send_or_unavailable(&self.read_sender, msg).await?;
+        Ok(recv_or_unavailable(receiver).await??)
     }
 
     pub async fn update(
@@ -381,22 +551,22 @@ impl IndexActorHandle {
     ) -> anyhow::Result<UpdateResult> {
         let (ret, receiver) = oneshot::channel();
         let msg = IndexMsg::Update { ret, meta, data };
-        let _ = self.read_sender.send(msg).await;
-        Ok(receiver.await.expect("IndexActor has been killed")?)
+        send_or_unavailable(&self.read_sender, msg).await?;
+        Ok(recv_or_unavailable(receiver).await??)
     }
 
     pub async fn search(&self, uuid: Uuid, query: SearchQuery) -> Result<SearchResult> {
         let (ret, receiver) = oneshot::channel();
         let msg = IndexMsg::Search { uuid, query, ret };
-        let _ = self.read_sender.send(msg).await;
-        Ok(receiver.await.expect("IndexActor has been killed")?)
+        send_or_unavailable(&self.read_sender, msg).await?;
+        Ok(recv_or_unavailable(receiver).await??)
     }
 
     pub async fn settings(&self, uuid: Uuid) -> Result<Settings> {
         let (ret, receiver) = oneshot::channel();
         let msg = IndexMsg::Settings { uuid, ret };
-        let _ = self.read_sender.send(msg).await;
-        Ok(receiver.await.expect("IndexActor has been killed")?)
+        send_or_unavailable(&self.read_sender, msg).await?;
+        Ok(recv_or_unavailable(receiver).await??)
     }
 
     pub async fn documents(
@@ -414,8 +584,8 @@ impl IndexActorHandle {
             attributes_to_retrieve,
             limit,
         };
-        let _ = self.read_sender.send(msg).await;
-        Ok(receiver.await.expect("IndexActor has been killed")?)
+        send_or_unavailable(&self.read_sender, msg).await?;
+        Ok(recv_or_unavailable(receiver).await??)
     }
 
     pub async fn document(
@@ -431,53 +601,143 @@ impl IndexActorHandle {
             doc_id,
             attributes_to_retrieve,
         };
-        let _ = self.read_sender.send(msg).await;
-        Ok(receiver.await.expect("IndexActor has been killed")?)
+        send_or_unavailable(&self.read_sender, msg).await?;
+        Ok(recv_or_unavailable(receiver).await??)
     }
 
     pub async fn delete(&self, uuid: Uuid) -> Result<()> {
         let (ret, receiver) = oneshot::channel();
         let msg = IndexMsg::Delete { uuid, ret };
-        let _ = self.read_sender.send(msg).await;
-        Ok(receiver.await.expect("IndexActor has been killed")?)
+        send_or_unavailable(&self.read_sender, msg).await?;
+        Ok(recv_or_unavailable(receiver).await??)
     }
 
     pub async fn get_index_meta(&self, uuid: Uuid) -> Result<Option<IndexMeta>> {
         let (ret, receiver) = oneshot::channel();
         let msg = IndexMsg::GetMeta { uuid, ret };
-        let _ = self.read_sender.send(msg).await;
-        Ok(receiver.await.expect("IndexActor has been killed")?)
+        send_or_unavailable(&self.read_sender, msg).await?;
+        Ok(recv_or_unavailable(receiver).await??)
+    }
+
+    /// Routed through `write_sender` so a snapshot can't run alongside a resize, a snapshot load
+    /// or a document clear/delete, but note `update()` travels on `read_sender`, and evictions
+    /// can be triggered by read-channel messages too: an in-flight update can still run concurrently
+    /// with a snapshot of the same index. That's fine because `copy_to_path` takes its own read
+    /// transaction and LMDB's MVCC guarantees it sees a consistent point-in-time view regardless.
+    pub async fn snapshot(&self, uuid: Uuid, dst: PathBuf) -> Result<()> {
+        let (ret, receiver) = oneshot::channel();
+        let msg = IndexMsg::Snapshot { uuid, dst, ret };
+        send_or_unavailable(&self.write_sender, msg).await?;
+        Ok(recv_or_unavailable(receiver).await??)
+    }
+
+    /// Restores an index from a directory produced by `snapshot`.
+    pub async fn load_snapshot(&self, uuid: Uuid, src: PathBuf) -> Result<()> {
+        let (ret, receiver) = oneshot::channel();
+        let msg = IndexMsg::LoadSnapshot { uuid, src, ret };
+        send_or_unavailable(&self.write_sender, msg).await?;
+        Ok(recv_or_unavailable(receiver).await??)
+    }
+
+    pub async fn resize_index(&self, uuid: Uuid, new_size: usize) -> Result<()> {
+        let (ret, receiver) = oneshot::channel();
+        let msg = IndexMsg::ResizeIndex {
+            uuid,
+            new_size,
+            ret,
+        };
+        send_or_unavailable(&self.write_sender, msg).await?;
+        Ok(recv_or_unavailable(receiver).await??)
+    }
+
+    pub async fn clear_documents(&self, uuid: Uuid) -> Result<()> {
+        let (ret, receiver) = oneshot::channel();
+        let msg = IndexMsg::ClearDocuments { uuid, ret };
+        send_or_unavailable(&self.write_sender, msg).await?;
+        Ok(recv_or_unavailable(receiver).await??)
+    }
+
+    pub async fn delete_documents(&self, uuid: Uuid, external_ids: Vec<String>) -> Result<()> {
+        let (ret, receiver) = oneshot::channel();
+        let msg = IndexMsg::DeleteDocuments {
+            uuid,
+            external_ids,
+            ret,
+        };
+        send_or_unavailable(&self.write_sender, msg).await?;
+        Ok(recv_or_unavailable(receiver).await??)
     }
 }
 
+/// An `Index` handle held by the store, tagged with the tick at which it was last accessed so
+/// the store can pick an eviction candidate without taking a global write lock on every read.
+struct IndexEntry {
+    index: Index,
+    last_accessed: AtomicU64,
+}
+
 struct HeedIndexStore {
     env: Env,
     db: Database<ByteSlice, SerdeBincode<IndexMeta>>,
-    index_store: AsyncMap<Uuid, Index>,
+    index_store: DashMap<Uuid, IndexEntry>,
+    /// Monotonic counter handed out on every access; the entry holding the smallest value is the
+    /// least-recently-used one.
+    clock: AtomicU64,
+    /// Per-uuid lock serializing a `resize` against a concurrent `get` reopening the same index.
+    /// Without it, a `get` racing a `resize` can observe the index missing from `index_store`
+    /// right after `resize` removed it, reopen it itself using the *old* `map_size` out of
+    /// `get_meta`, and reinsert it — silently undoing the resize for whichever of the two
+    /// `insert` calls loses the race.
+    resize_locks: DashMap<Uuid, Arc<tokio::sync::Mutex<()>>>,
+    max_open_indexes: usize,
     path: PathBuf,
 }
 
 impl HeedIndexStore {
-    fn new(path: impl AsRef<Path>) -> anyhow::Result<Self> {
+    fn new(path: impl AsRef<Path>, max_open_indexes: usize) -> anyhow::Result<Self> {
         let mut options = EnvOpenOptions::new();
         options.map_size(1_073_741_824); //1GB
         let path = path.as_ref().join("indexes/");
         create_dir_all(&path)?;
         let env = options.open(&path)?;
         let db = env.create_database(None)?;
-        let index_store = Arc::new(RwLock::new(HashMap::new()));
+        let index_store = DashMap::new();
         Ok(Self {
             env,
             db,
             index_store,
+            clock: AtomicU64::new(0),
+            resize_locks: DashMap::new(),
+            max_open_indexes,
             path,
         })
     }
-}
 
-#[async_trait::async_trait]
-impl IndexStore for HeedIndexStore {
-    async fn create_index(&self, uuid: Uuid, primary_key: Option<String>) -> Result<IndexMeta> {
+    /// Returns the mutex guarding reopen/resize races for `uuid`, creating it on first use.
+    fn resize_lock(&self, uuid: Uuid) -> Arc<tokio::sync::Mutex<()>> {
+        self.resize_locks
+            .entry(uuid)
+            .or_insert_with(|| Arc::new(tokio::sync::Mutex::new(())))
+            .clone()
+    }
+
+    /// Hands out a fresh tick from the monotonic clock, without touching the map.
+    fn next_tick(&self) -> u64 {
+        self.clock.fetch_add(1, Ordering::Relaxed)
+    }
+
+    /// Writes a fresh `IndexMeta` record and opens its `Index`, without inserting it into
+    /// `index_store`. Callers that need the `Index` handle right away should use the one
+    /// returned here instead of reading it back out of the map afterwards: a concurrent
+    /// `insert` for a different uuid can run `evict_if_needed` in between and, with a small
+    /// `max_open_indexes`, pick this brand new entry as the eviction candidate before the
+    /// caller gets a chance to read it.
+    async fn create_index_raw(
+        &self,
+        uuid: Uuid,
+        primary_key: Option<String>,
+        map_size: usize,
+    ) -> Result<(Index, IndexMeta)> {
         let path = self.path.join(format!("index-{}", uuid));
 
         if path.exists() {
@@ -486,27 +746,89 @@ impl IndexStore for HeedIndexStore {
 
         let env = self.env.clone();
         let db = self.db.clone();
-        let (index, meta) = spawn_blocking(move || -> Result<(Index, IndexMeta)> {
+        let result = spawn_blocking(move || -> Result<(Index, IndexMeta)> {
             let now = Utc::now();
             let meta = IndexMeta {
                 uuid: uuid.clone(),
                 created_at: now.clone(),
                 updated_at: now,
                 primary_key,
+                map_size,
             };
             let mut txn = env.write_txn()?;
             db.put(&mut txn, uuid.as_bytes(), &meta)?;
             txn.commit()?;
 
-            let index = open_index(&path, 4096 * 100_000)?;
+            let index = open_index(&path, map_size)?;
 
             Ok((index, meta))
         })
-        .await
-        .expect("thread died")?;
+        .await;
+        flatten_blocking(result)
+    }
 
-        self.index_store.write().await.insert(uuid.clone(), index);
+    async fn insert(&self, uuid: Uuid, index: Index) {
+        let last_accessed = AtomicU64::new(self.next_tick());
+        self.index_store.insert(
+            uuid,
+            IndexEntry {
+                index,
+                last_accessed,
+            },
+        );
+        self.evict_if_needed().await;
+    }
+
+    /// If the store holds more than `max_open_indexes` handles, closes the least-recently-used
+    /// one, the same way `delete` tears down a removed index. The teardown itself is detached
+    /// onto its own task, the same way `handle_delete` detaches it, so `insert` (and everything
+    /// that awaits it: `get`, `create_index`, `update_index`, `resize`, `load_snapshot`) isn't
+    /// blocked for as long as it takes the victim to drain its current holders. The per-uuid
+    /// `resize_lock` is held until the close finishes so a concurrent `get`/`resize` for that same
+    /// `uuid` still can't reopen the LMDB env at `index-{uuid}` before this one has fully released
+    /// it.
+    async fn evict_if_needed(&self) {
+        if self.index_store.len() <= self.max_open_indexes {
+            return;
+        }
+
+        let lru = self
+            .index_store
+            .iter()
+            .min_by_key(|entry| entry.last_accessed.load(Ordering::Relaxed))
+            .map(|entry| *entry.key());
+
+        if let Some(uuid) = lru {
+            if let Some((_, entry)) = self.index_store.remove(&uuid) {
+                let guard = self.resize_lock(uuid).lock_owned().await;
+                tokio::task::spawn(async move {
+                    let index = get_arc_ownership_blocking(entry.index.0).await;
+                    if spawn_blocking(move || {
+                        index.prepare_for_closing().wait();
+                        debug!("Evicted idle index {}", uuid);
+                    })
+                    .await
+                    .is_err()
+                    {
+                        debug!("index closing thread panicked while evicting {}", uuid);
+                    }
+                    drop(guard);
+                });
+            }
+        }
+    }
+}
 
+#[async_trait::async_trait]
+impl IndexStore for HeedIndexStore {
+    async fn create_index(
+        &self,
+        uuid: Uuid,
+        primary_key: Option<String>,
+        map_size: usize,
+    ) -> Result<IndexMeta> {
+        let (index, meta) = self.create_index_raw(uuid, primary_key, map_size).await?;
+        self.insert(uuid, index).await;
         Ok(meta)
     }
 
@@ -515,24 +837,27 @@ impl IndexStore for HeedIndexStore {
         F: FnOnce(Index) -> Result<R> + Send + Sync + 'static,
         R: Sync + Send + 'static,
     {
-        let guard = self.index_store.read().await;
-        let index = match guard.get(&uuid) {
-            Some(index) => index.clone(),
-            None => {
-                drop(guard);
-                self.create_index(uuid.clone(), None).await?;
-                self.index_store
-                    .read()
-                    .await
-                    .get(&uuid)
-                    .expect("Index should exist")
-                    .clone()
+        let index = match self.index_store.get(&uuid) {
+            Some(entry) => {
+                entry.last_accessed.store(self.next_tick(), Ordering::Relaxed);
+                entry.index.clone()
             }
+            // Not resident: reopen it if it already exists on disk (it may simply have been
+            // evicted by the LRU), and only create a brand new one if it really doesn't exist.
+            None => match self.get(uuid).await? {
+                Some(index) => index,
+                None => {
+                    let (index, _meta) =
+                        self.create_index_raw(uuid, None, DEFAULT_MAP_SIZE).await?;
+                    self.insert(uuid, index.clone()).await;
+                    index
+                }
+            },
         };
 
         let env = self.env.clone();
         let db = self.db.clone();
-        spawn_blocking(move || {
+        let result = spawn_blocking(move || {
             let mut txn = env.write_txn()?;
             let mut meta = db.get(&txn, uuid.as_bytes())?.expect("unexisting index");
             match f(index) {
@@ -545,63 +870,204 @@ impl IndexStore for HeedIndexStore {
                 Err(e) => Err(e),
             }
         })
-        .await
-        .expect("thread died")
+        .await;
+        flatten_blocking(result)
     }
 
     async fn get(&self, uuid: Uuid) -> Result<Option<Index>> {
-        let guard = self.index_store.read().await;
-        match guard.get(&uuid) {
-            Some(index) => Ok(Some(index.clone())),
-            None => {
-                // drop the guard here so we can perform the write after without deadlocking;
-                drop(guard);
-                let path = self.path.join(format!("index-{}", uuid));
-                if !path.exists() {
-                    return Ok(None);
-                }
+        // Update the recency tick through the `Ref` we already hold instead of issuing a second
+        // `get` on the same shard: DashMap's shard lock is not reentrant, so nesting a `get`
+        // inside another `get` on the same key can deadlock once a writer is queued behind it.
+        if let Some(entry) = self.index_store.get(&uuid) {
+            entry.last_accessed.store(self.next_tick(), Ordering::Relaxed);
+            return Ok(Some(entry.index.clone()));
+        }
 
-                // TODO: set this info from the database
-                let index = spawn_blocking(|| open_index(path, 4096 * 100_000))
-                    .await
-                    .expect("thread died")?;
-                self.index_store
-                    .write()
-                    .await
-                    .insert(uuid.clone(), index.clone());
-                println!("here");
-                Ok(Some(index))
-            }
+        // Not resident: hold the per-uuid lock across the reopen so it can't race a concurrent
+        // `resize` of the same index. If `resize` got there first, it holds this same lock for
+        // the whole remove-reopen-reinsert sequence, so by the time we acquire it the entry is
+        // either already back in `index_store` (common case, just return it) or `resize` hasn't
+        // started yet and we open at the current `map_size` ourselves.
+        let lock = self.resize_lock(uuid);
+        let _guard = lock.lock().await;
+
+        if let Some(entry) = self.index_store.get(&uuid) {
+            entry.last_accessed.store(self.next_tick(), Ordering::Relaxed);
+            return Ok(Some(entry.index.clone()));
         }
+
+        let path = self.path.join(format!("index-{}", uuid));
+        if !path.exists() {
+            return Ok(None);
+        }
+
+        let map_size = self
+            .get_meta(uuid)
+            .await?
+            .map(|meta| meta.map_size)
+            .unwrap_or(DEFAULT_MAP_SIZE);
+        let result = spawn_blocking(move || open_index(path, map_size)).await;
+        let index = flatten_blocking(result)?;
+        self.insert(uuid, index.clone()).await;
+        Ok(Some(index))
     }
 
     async fn delete(&self, uuid: Uuid) -> Result<Option<Index>> {
         let env = self.env.clone();
         let db = self.db.clone();
         let db_path = self.path.join(format!("index-{}", uuid));
-        spawn_blocking(move || -> Result<()> {
+        let result = spawn_blocking(move || -> Result<()> {
             let mut txn = env.write_txn()?;
             db.delete(&mut txn, uuid.as_bytes())?;
             txn.commit()?;
             remove_dir_all(db_path).unwrap();
             Ok(())
         })
-        .await
-        .expect("thread died")?;
-        let index = self.index_store.write().await.remove(&uuid);
+        .await;
+        flatten_blocking(result)?;
+        let index = self.index_store.remove(&uuid).map(|(_, entry)| entry.index);
+        // The uuid is gone for good; drop its lock entry too so resize_locks doesn't grow
+        // without bound over indexes created and deleted across the server's lifetime.
+        self.resize_locks.remove(&uuid);
         Ok(index)
     }
 
     async fn get_meta(&self, uuid: Uuid) -> Result<Option<IndexMeta>> {
         let env = self.env.clone();
         let db = self.db.clone();
-        spawn_blocking(move || {
+        let result = spawn_blocking(move || -> Result<Option<IndexMeta>> {
+            // `get_meta` is on the hot path (every evicted-index reopen goes through it), so the
+            // happy path stays a plain read_txn, same as baseline: LMDB allows only one live
+            // writer per env, and this one is shared by every index. Only the legacy-decode
+            // fallback below needs a write_txn, to rewrite the migrated record.
             let txn = env.read_txn()?;
-            let meta = db.get(&txn, uuid.as_bytes())?;
+            match db.get(&txn, uuid.as_bytes()) {
+                Ok(meta) => Ok(meta),
+                // Pre-migration record without `map_size`: decode it as the legacy shape and
+                // rewrite it in the current format so future reads no longer take this path.
+                Err(_) => {
+                    let legacy_db = db.remap_data_type::<SerdeBincode<IndexMetaV0>>();
+                    let old = match legacy_db.get(&txn, uuid.as_bytes())? {
+                        Some(old) => old,
+                        None => return Ok(None),
+                    };
+                    drop(txn);
+
+                    let meta: IndexMeta = old.into();
+                    let mut txn = env.write_txn()?;
+                    db.put(&mut txn, uuid.as_bytes(), &meta)?;
+                    txn.commit()?;
+                    Ok(Some(meta))
+                }
+            }
+        })
+        .await;
+        flatten_blocking(result)
+    }
+
+    async fn snapshot(&self, uuid: Uuid, dst: PathBuf) -> Result<()> {
+        let index = self.get(uuid).await?.ok_or(IndexError::UnexistingIndex)?;
+        let meta = self
+            .get_meta(uuid)
+            .await?
+            .ok_or(IndexError::UnexistingIndex)?;
+        let index_dst = dst.join(format!("index-{}", uuid));
+
+        let result = spawn_blocking(move || -> Result<()> {
+            create_dir_all(&index_dst).map_err(|e| IndexError::Error(e.into()))?;
+            index
+                .0
+                .env
+                .copy_to_path(index_dst.join("data.mdb"), CompactionOption::Enabled)?;
+
+            let meta_file = File::create(index_dst.join("meta.json"))
+                .map_err(|e| IndexError::Error(e.into()))?;
+            serde_json::to_writer(meta_file, &meta).map_err(|e| IndexError::Error(e.into()))?;
+
+            Ok(())
+        })
+        .await;
+        flatten_blocking(result)
+    }
+
+    /// Restores an index from a directory produced by `snapshot`: copies the LMDB data into the
+    /// live `path` and reinserts the `IndexMeta` so it becomes reachable through `get` like any
+    /// other index. Refuses to overwrite an index that already exists, the same way
+    /// `create_index` does, since overwriting the data file of a live index out from under its
+    /// mmap would corrupt it.
+    async fn load_snapshot(&self, uuid: Uuid, src: PathBuf) -> Result<()> {
+        let index_path = self.path.join(format!("index-{}", uuid));
+        if index_path.exists() {
+            return Err(IndexError::IndexAlreadyExists);
+        }
+
+        let env = self.env.clone();
+        let db = self.db.clone();
+        let dst_path = index_path.clone();
+        // `snapshot` writes each index into its own `index-{uuid}` subdirectory of `dst`.
+        let src = src.join(format!("index-{}", uuid));
+
+        let result = spawn_blocking(move || -> Result<IndexMeta> {
+            let meta_file =
+                File::open(src.join("meta.json")).map_err(|e| IndexError::Error(e.into()))?;
+            let meta: IndexMeta =
+                serde_json::from_reader(meta_file).map_err(|e| IndexError::Error(e.into()))?;
+
+            create_dir_all(&dst_path).map_err(|e| IndexError::Error(e.into()))?;
+            std::fs::copy(src.join("data.mdb"), dst_path.join("data.mdb"))
+                .map_err(|e| IndexError::Error(e.into()))?;
+
+            let mut txn = env.write_txn()?;
+            db.put(&mut txn, uuid.as_bytes(), &meta)?;
+            txn.commit()?;
+
             Ok(meta)
         })
-        .await
-        .expect("thread died")
+        .await;
+        let meta = flatten_blocking(result)?;
+
+        let result = spawn_blocking(move || open_index(&index_path, meta.map_size)).await;
+        let index = flatten_blocking(result)?;
+        self.insert(uuid, index).await;
+        Ok(())
+    }
+
+    async fn resize(&self, uuid: Uuid, new_size: usize) -> Result<()> {
+        // Held for the whole remove-reopen-reinsert sequence below, so a concurrent `get` for
+        // this uuid can't reopen the index at the old `map_size` in the gap between us removing
+        // the entry and putting the resized one back.
+        let lock = self.resize_lock(uuid);
+        let _guard = lock.lock().await;
+
+        if let Some((_, entry)) = self.index_store.remove(&uuid) {
+            let index = get_arc_ownership_blocking(entry.index.0).await;
+            if spawn_blocking(move || index.prepare_for_closing().wait())
+                .await
+                .is_err()
+            {
+                debug!("index closing thread panicked while resizing {}", uuid);
+            }
+        }
+
+        let path = self.path.join(format!("index-{}", uuid));
+        let env = self.env.clone();
+        let db = self.db.clone();
+        let result = spawn_blocking(move || -> Result<Index> {
+            let mut txn = env.write_txn()?;
+            let mut meta = db
+                .get(&txn, uuid.as_bytes())?
+                .ok_or(IndexError::UnexistingIndex)?;
+            meta.map_size = new_size;
+            db.put(&mut txn, uuid.as_bytes(), &meta)?;
+            txn.commit()?;
+
+            open_index(&path, new_size)
+        })
+        .await;
+        let index = flatten_blocking(result)?;
+
+        self.insert(uuid, index).await;
+        Ok(())
     }
 }
 
@@ -612,3 +1078,147 @@ fn open_index(path: impl AsRef<Path>, size: usize) -> Result<Index> {
     let index = milli::Index::new(options, &path).map_err(|e| IndexError::Error(e))?;
     Ok(Index(Arc::new(index)))
 }
+
+#[cfg(test)]
+mod test {
+    use tempfile::tempdir;
+
+    use super::*;
+
+    fn tmp_store(max_open_indexes: usize) -> (tempfile::TempDir, HeedIndexStore) {
+        let dir = tempdir().unwrap();
+        let store = HeedIndexStore::new(dir.path(), max_open_indexes).unwrap();
+        (dir, store)
+    }
+
+    #[tokio::test]
+    async fn evicted_index_reopens_transparently_through_get() {
+        let (_dir, store) = tmp_store(1);
+        let first = Uuid::new_v4();
+        let second = Uuid::new_v4();
+
+        store.create_index(first, None, DEFAULT_MAP_SIZE).await.unwrap();
+        store.create_index(second, None, DEFAULT_MAP_SIZE).await.unwrap();
+
+        // Only one handle stays resident with max_open_indexes == 1, so `first` was evicted to
+        // make room for `second`.
+        assert_eq!(store.index_store.len(), 1);
+        assert!(store.index_store.get(&first).is_none());
+
+        // `get` transparently reopens the evicted index instead of reporting it missing.
+        assert!(store.get(first).await.unwrap().is_some());
+    }
+
+    #[tokio::test]
+    async fn snapshot_round_trips_into_a_fresh_store() {
+        let (_src_dir, src) = tmp_store(10);
+        let (_dst_dir, dst) = tmp_store(10);
+
+        let uuid = Uuid::new_v4();
+        src.create_index(uuid, Some("id".into()), DEFAULT_MAP_SIZE)
+            .await
+            .unwrap();
+
+        let snapshot_dir = tempdir().unwrap();
+        src.snapshot(uuid, snapshot_dir.path().to_owned())
+            .await
+            .unwrap();
+
+        dst.load_snapshot(uuid, snapshot_dir.path().to_owned())
+            .await
+            .unwrap();
+
+        let meta = dst.get_meta(uuid).await.unwrap().unwrap();
+        assert_eq!(meta.uuid, uuid);
+        assert_eq!(meta.primary_key.as_deref(), Some("id"));
+        assert!(dst.get(uuid).await.unwrap().is_some());
+    }
+
+    #[tokio::test]
+    async fn resize_updates_meta_and_keeps_the_index_usable() {
+        let (_dir, store) = tmp_store(10);
+        let uuid = Uuid::new_v4();
+        store
+            .create_index(uuid, None, DEFAULT_MAP_SIZE)
+            .await
+            .unwrap();
+
+        let new_size = DEFAULT_MAP_SIZE * 2;
+        store.resize(uuid, new_size).await.unwrap();
+
+        let meta = store.get_meta(uuid).await.unwrap().unwrap();
+        assert_eq!(meta.map_size, new_size);
+        assert!(store.get(uuid).await.unwrap().is_some());
+    }
+
+    #[tokio::test]
+    async fn legacy_meta_without_map_size_is_migrated_on_read() {
+        let (_dir, store) = tmp_store(10);
+        let uuid = Uuid::new_v4();
+        let now = Utc::now();
+
+        let legacy = IndexMetaV0 {
+            uuid,
+            created_at: now,
+            updated_at: now,
+            primary_key: None,
+        };
+        {
+            let mut txn = store.env.write_txn().unwrap();
+            let legacy_db = store.db.remap_data_type::<SerdeBincode<IndexMetaV0>>();
+            legacy_db.put(&mut txn, uuid.as_bytes(), &legacy).unwrap();
+            txn.commit().unwrap();
+        }
+
+        let meta = store.get_meta(uuid).await.unwrap().unwrap();
+        assert_eq!(meta.map_size, DEFAULT_MAP_SIZE);
+
+        // The migrated record decodes as current-format IndexMeta directly now.
+        let txn = store.env.read_txn().unwrap();
+        assert!(store.db.get(&txn, uuid.as_bytes()).unwrap().is_some());
+    }
+
+    #[tokio::test]
+    async fn clear_and_delete_documents_commit_through_update_index() {
+        let (_dir, store) = tmp_store(10);
+        let uuid = Uuid::new_v4();
+        store
+            .create_index(uuid, None, DEFAULT_MAP_SIZE)
+            .await
+            .unwrap();
+
+        let before = store.get_meta(uuid).await.unwrap().unwrap().updated_at;
+
+        store
+            .update_index(uuid, |index| {
+                let mut wtxn = index.0.env.write_txn()?;
+                milli::update::ClearDocuments::new(&mut wtxn, &index.0)
+                    .execute()
+                    .map_err(|e| IndexError::Error(e.into()))?;
+                wtxn.commit()?;
+                Ok(())
+            })
+            .await
+            .unwrap();
+
+        store
+            .update_index(uuid, |index| {
+                let mut wtxn = index.0.env.write_txn()?;
+                let mut builder = milli::update::DeleteDocuments::new(&mut wtxn, &index.0)
+                    .map_err(|e| IndexError::Error(e.into()))?;
+                builder.delete_external_id("targeted-id");
+                builder
+                    .execute()
+                    .map_err(|e| IndexError::Error(e.into()))?;
+                wtxn.commit()?;
+                Ok(())
+            })
+            .await
+            .unwrap();
+
+        // Both operations actually ran through update_index's write path: the meta timestamp it
+        // bumps on a successful commit moved forward.
+        let after = store.get_meta(uuid).await.unwrap().unwrap().updated_at;
+        assert!(after > before);
+    }
+}
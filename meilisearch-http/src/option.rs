@@ -0,0 +1,55 @@
+use std::num::NonZeroUsize;
+
+use structopt::StructOpt;
+
+/// Number of `Index` handles the store keeps memory-mapped at once before it starts evicting the
+/// least-recently-used one, when `--max-open-indexes` isn't passed.
+const DEFAULT_MAX_OPEN_INDEXES: usize = 500;
+
+/// Default ceiling, in bytes, on the indexing pipeline's in-memory sorter before it flushes to
+/// disk, when `--max-indexing-memory` isn't passed.
+const DEFAULT_MAX_INDEXING_MEMORY: usize = 2 * 1024 * 1024 * 1024;
+
+/// Options controlling how the indexing pipeline and the index store manage their resources.
+#[derive(Debug, Clone, StructOpt)]
+pub struct IndexerOpts {
+    /// The maximum number of `Index` handles the store keeps memory-mapped at once before it
+    /// starts evicting the least-recently-used one.
+    #[structopt(long, env = "MEILI_MAX_OPEN_INDEXES", default_value = "500")]
+    pub max_open_indexes: usize,
+
+    /// The maximum amount of memory, in bytes, the indexing pipeline's sorter is allowed to use
+    /// before it flushes its chunks to disk.
+    #[structopt(long, env = "MEILI_MAX_INDEXING_MEMORY", default_value = "2147483648")]
+    pub max_memory: usize,
+
+    /// The maximum number of chunks the indexing pipeline keeps on disk while merging. Left
+    /// unbounded by default.
+    #[structopt(long, env = "MEILI_MAX_NB_CHUNKS")]
+    pub max_nb_chunks: Option<usize>,
+
+    /// The compression algorithm used for intermediate indexing chunks written to disk.
+    #[structopt(long, env = "MEILI_CHUNK_COMPRESSION_TYPE", default_value = "snappy")]
+    pub chunk_compression_type: String,
+
+    /// The compression level used when `chunk_compression_type` supports one.
+    #[structopt(long, env = "MEILI_CHUNK_COMPRESSION_LEVEL")]
+    pub chunk_compression_level: Option<u32>,
+
+    /// Number of parallel jobs used by the indexing pipeline; defaults to the number of CPUs.
+    #[structopt(long, env = "MEILI_INDEXING_JOBS")]
+    pub indexing_jobs: Option<NonZeroUsize>,
+}
+
+impl Default for IndexerOpts {
+    fn default() -> Self {
+        Self {
+            max_open_indexes: DEFAULT_MAX_OPEN_INDEXES,
+            max_memory: DEFAULT_MAX_INDEXING_MEMORY,
+            max_nb_chunks: None,
+            chunk_compression_type: "snappy".into(),
+            chunk_compression_level: None,
+            indexing_jobs: None,
+        }
+    }
+}